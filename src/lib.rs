@@ -1,65 +1,295 @@
 use std::{
+    cell::{RefCell, UnsafeCell},
+    mem::MaybeUninit,
+    ops::Deref,
     ptr::null_mut,
-    sync::atomic::{AtomicPtr as RawAtomicPtr, AtomicUsize, Ordering::SeqCst},
+    sync::atomic::{AtomicPtr as RawAtomicPtr, AtomicU8, AtomicUsize, Ordering::SeqCst},
 };
 
-use crossbeam_utils::CachePadded;
-use haphazard::{AtomicPtr as HpAtomicPtr, HazardPointer};
+use crossbeam_utils::{Backoff, CachePadded};
+use haphazard::{raw::Pointer, AtomicPtr as HpAtomicPtr, Global, HazardPointer};
 
+/// Default per-node buffer size, used when [`FAAAQueue`]'s `N` const parameter isn't specified.
 const BUFFER_SIZE: usize = 1024;
+/// Upper bound on how many drained nodes a queue keeps around for reuse. Past this point,
+/// drained nodes are freed instead of pooled so that an idle queue releases its memory.
+const FREELIST_CAP: usize = 64;
 
-struct Node<T> {
+/// Spin while `backoff` is still within its bounded-spin budget, then start yielding the thread
+/// once that budget is exhausted, so heavily-contended retry loops stop burning CPU.
+fn pace(backoff: &Backoff) {
+    // `snooze()` is what actually advances `backoff` from spinning to yielding the thread once
+    // its spin budget is exhausted; `spin()` alone plateaus forever and never reaches that state.
+    backoff.snooze();
+}
+
+/// A well-known dangling, non-null pointer value `dequeue_with` swaps into a slot once its item
+/// has been taken, so an `IntoIter` later walking the same array can tell "already dequeued"
+/// apart from "never published" (null) without racing the real item pointer.
+fn taken_sentinel<T>() -> *mut T {
+    std::ptr::dangling_mut::<u64>() as *mut T
+}
+
+thread_local! {
+    // Each thread keeps a single hazard pointer around and reuses it across every
+    // `enqueue`/`dequeue` call (on any queue), rather than acquiring a fresh one from the
+    // domain every time.
+    static HAZARD_POINTER: RefCell<HazardPointer<'static>> = RefCell::new(HazardPointer::new());
+    // A second, separate slot for `Freelist::pop`: it's called from within `enqueue_with` while
+    // that call's own `HAZARD_POINTER` borrow is still held, so reusing the same thread-local
+    // there would hit `RefCell`'s double-borrow panic. Still reused across calls per thread,
+    // same as `HAZARD_POINTER`, just keyed separately.
+    static FREELIST_HAZARD_POINTER: RefCell<HazardPointer<'static>> = RefCell::new(HazardPointer::new());
+}
+
+struct Node<T, const N: usize> {
     enqueue_index: CachePadded<AtomicUsize>,
     dequeue_index: CachePadded<AtomicUsize>,
-    next: CachePadded<HpAtomicPtr<Node<T>>>,
-    array: [RawAtomicPtr<T>; BUFFER_SIZE],
+    next: CachePadded<HpAtomicPtr<Node<T, N>>>,
+    array: [RawAtomicPtr<T>; N],
+    // Which freelist a fully-drained copy of this node should be recycled into. Stored as an
+    // atomic pointer (rather than `*const Freelist<T, N>`) purely so `Node<T, N>` keeps
+    // auto-deriving `Send`, same as its other raw-pointer-bearing fields.
+    freelist: RawAtomicPtr<Freelist<T, N>>,
 }
 
+/// A lock-free Treiber stack of fully-drained [`Node`]s, so a queue under steady load can reuse
+/// a buffer instead of paying for a fresh allocation every `N` operations.
 #[derive(Debug)]
-pub struct FAAAQueue<T> {
-    head: HpAtomicPtr<Node<T>>,
-    tail: HpAtomicPtr<Node<T>>,
+struct Freelist<T, const N: usize> {
+    head: HpAtomicPtr<Node<T, N>>,
+    len: AtomicUsize,
+}
+
+impl<T, const N: usize> Freelist<T, N> {
+    fn new() -> Self {
+        Self {
+            head: unsafe { HpAtomicPtr::new(null_mut()) },
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pop a recycled node off the pool, if one is available.
+    ///
+    /// Protected by this thread's `FREELIST_HAZARD_POINTER`: a popped node's `next` link must not
+    /// be read while some other thread could be concurrently popping (and then reusing) the same
+    /// node, which is exactly the hazard-pointer guarantee `head`/`tail` already rely on.
+    fn pop(&self) -> Option<*mut Node<T, N>> {
+        FREELIST_HAZARD_POINTER.with(|hp| {
+            let mut hp = hp.borrow_mut();
+            loop {
+                let head = self.head.safe_load(&mut hp)?;
+                let head_ptr = head as *const Node<T, N> as *mut Node<T, N>;
+                let next = head.next.load_ptr();
+                if unsafe { self.head.compare_exchange_ptr(head_ptr, next) }.is_ok() {
+                    self.len.fetch_sub(1, SeqCst);
+                    // The popped node is ours now; leaving it protected would keep it from being
+                    // reclaimed once it is later drained and retired.
+                    hp.reset_protection();
+                    return Some(head_ptr);
+                }
+            }
+        })
+    }
+
+    /// Push a fully-drained node onto the pool, unless it's already at [`FREELIST_CAP`], in
+    /// which case the node is freed instead so an idle queue doesn't hold onto memory forever.
+    fn push(&self, node: *mut Node<T, N>) {
+        if self
+            .len
+            .fetch_update(SeqCst, SeqCst, |len| (len < FREELIST_CAP).then_some(len + 1))
+            .is_err()
+        {
+            unsafe { drop(Box::from_raw(node)) };
+            return;
+        }
+        loop {
+            let head = self.head.load_ptr();
+            unsafe { (*node).next.store_ptr(head) };
+            if unsafe { self.head.compare_exchange_ptr(head, node) }.is_ok() {
+                return;
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Freelist<T, N> {
+    fn drop(&mut self) {
+        let mut node = self.head.load_ptr();
+        while !node.is_null() {
+            let next = unsafe { (*node).next.load_ptr() };
+            unsafe { drop(Box::from_raw(node)) };
+            node = next;
+        }
+    }
 }
 
-impl<T> Node<T> {
-    fn new(data_ptr: *mut T) -> Self {
+/// The [`haphazard::raw::Pointer`] used for `FAAAQueue`'s `head`, so that once a fully-drained
+/// node is safe to reclaim (i.e. no hazard pointer protects it any longer), the domain recycles
+/// it back into the owning queue's [`Freelist`] (which frees it instead once it already holds
+/// [`FREELIST_CAP`] nodes).
+struct PooledNode<T, const N: usize>(*mut Node<T, N>);
+
+impl<T, const N: usize> Deref for PooledNode<T, N> {
+    type Target = Node<T, N>;
+    fn deref(&self) -> &Node<T, N> {
+        unsafe { &*self.0 }
+    }
+}
+
+// Safety: `PooledNode<T, N>` is only ever constructed from a `*mut Node<T, N>` that was itself
+// produced by `Box::new`, and round-trips through `into_raw`/`from_raw` exactly like
+// `Box<Node<T, N>>` does.
+unsafe impl<T, const N: usize> Pointer<Node<T, N>> for PooledNode<T, N> {
+    fn into_raw(self) -> *mut Node<T, N> {
+        let ptr = self.0;
+        std::mem::forget(self);
+        ptr
+    }
+
+    unsafe fn from_raw(ptr: *mut Node<T, N>) -> Self {
+        PooledNode(ptr)
+    }
+}
+
+impl<T, const N: usize> Drop for PooledNode<T, N> {
+    fn drop(&mut self) {
+        let freelist = unsafe { &*(*self.0).freelist.load(SeqCst) };
+        freelist.push(self.0);
+    }
+}
+
+/// A thread parked in [`FAAAQueue::dequeue_blocking`], linked into the queue's `waiters` Treiber
+/// stack while it waits to be told to check again.
+///
+/// `state` is the single source of truth for who frees the `Waiter`, and is only ever written by
+/// a `compare_exchange` race between exactly two parties: the waiting thread itself (trying
+/// [`Waiter::PARKED`] -> [`Waiter::CANCELLED`] if it finds an item on its own after linking in)
+/// and [`FAAAQueue::wake_one_waiter`] (trying [`Waiter::PARKED`] -> [`Waiter::FULFILLED`] after
+/// unlinking the node from the stack). Unlinking from the stack is always exclusive — only the
+/// `compare_exchange` winner on `self.waiters` ever observes a given node there — so whichever of
+/// the two loses its `state` CAS can infer the other side already has (or will have) exclusive
+/// access, and behaves accordingly:
+/// - If the waiting thread wins (`CANCELLED`), it returns without touching the `Waiter` again;
+///   freeing it becomes the job of whichever `wake_one_waiter` call eventually pops it (or
+///   [`free_waiters`] at `Drop`, if the queue is torn down first).
+/// - If `wake_one_waiter` wins (`FULFILLED`), it has already unlinked the node, so the waiting
+///   thread is the sole remaining owner once it observes `FULFILLED` and frees it itself.
+/// - If `wake_one_waiter`'s CAS *loses* (finds `CANCELLED`), it already unlinked the node a
+///   moment ago to attempt the CAS at all, so it alone frees it now, without waking the thread.
+struct Waiter {
+    thread: std::thread::Thread,
+    state: AtomicU8,
+    next: RawAtomicPtr<Waiter>,
+}
+
+impl Waiter {
+    const PARKED: u8 = 0;
+    const FULFILLED: u8 = 1;
+    const CANCELLED: u8 = 2;
+}
+
+#[derive(Debug)]
+pub struct FAAAQueue<T, const N: usize = BUFFER_SIZE> {
+    head: HpAtomicPtr<Node<T, N>, Global, PooledNode<T, N>>,
+    tail: HpAtomicPtr<Node<T, N>>,
+    // Boxed so the freelist has a stable address for `Node::freelist` to point at, even if the
+    // `FAAAQueue` itself is later moved.
+    freelist: Box<Freelist<T, N>>,
+    // Treiber stack of threads parked in `dequeue_blocking`, most-recently-parked first.
+    waiters: RawAtomicPtr<Waiter>,
+}
+
+impl<T, const N: usize> Node<T, N> {
+    fn new(data_ptr: *mut T, freelist: *mut Freelist<T, N>) -> Self {
         let mut node = Self {
             enqueue_index: CachePadded::new(1.into()),
             dequeue_index: CachePadded::new(0.into()),
             next: unsafe { CachePadded::new(HpAtomicPtr::new(core::ptr::null_mut())) },
-            array: [const { RawAtomicPtr::new(core::ptr::null_mut()) }; BUFFER_SIZE],
+            array: [const { RawAtomicPtr::new(core::ptr::null_mut()) }; N],
+            freelist: RawAtomicPtr::new(freelist),
         };
         // NOTE: Copies the address.
         node.array[0] = RawAtomicPtr::new(data_ptr);
         node
     }
 
-    fn empty() -> Self {
+    fn empty(freelist: *mut Freelist<T, N>) -> Self {
         Self {
             enqueue_index: CachePadded::new(0.into()),
             dequeue_index: CachePadded::new(0.into()),
             next: unsafe { CachePadded::new(HpAtomicPtr::new(core::ptr::null_mut())) },
-            array: [const { RawAtomicPtr::new(core::ptr::null_mut()) }; BUFFER_SIZE],
+            array: [const { RawAtomicPtr::new(core::ptr::null_mut()) }; N],
+            freelist: RawAtomicPtr::new(freelist),
         }
     }
+
+    /// Reinitialize a recycled node in place for reuse as a brand-new tail segment.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node popped from a [`Freelist`] (so no other thread holds a reference to
+    /// it), whose every published item was already taken by a consumer before it was retired.
+    unsafe fn reinit(node: *mut Node<T, N>, data_ptr: *mut T) {
+        let node = unsafe { &*node };
+        // Every slot a consumer actually read during this node's previous life holds
+        // `taken_sentinel()`, not null — clear all of them (not just slot 0) before
+        // publishing, or a later `enqueue` into a stale slot CAS-fails forever against the
+        // sentinel, and a later `dequeue` reads the stale sentinel back as if it were a real
+        // boxed `T`.
+        for slot in &node.array {
+            slot.store(core::ptr::null_mut(), SeqCst);
+        }
+        node.array[0].store(data_ptr, SeqCst);
+        unsafe { node.next.store_ptr(core::ptr::null_mut()) };
+        node.dequeue_index.store(0, SeqCst);
+        // Must be last: as soon as this is visible, concurrent enqueuers may start claiming
+        // slots in `array` before this thread has finished publishing the node.
+        node.enqueue_index.store(1, SeqCst);
+    }
 }
-impl<T> FAAAQueue<T> {
-    // TODO: Change so the user does not need to handle hazard pointers.
-    pub fn enqueue(&self, item: T, hp: &mut HazardPointer) {
+impl<T, const N: usize> FAAAQueue<T, N> {
+    /// Enqueue `item`, borrowing this thread's hazard pointer from a thread-local pool.
+    ///
+    /// This is the ergonomic entry point for the common case. Use [`FAAAQueue::enqueue_with`]
+    /// if you need to supply your own [`HazardPointer`] (one from the global domain, which is
+    /// the only domain this queue uses).
+    pub fn enqueue(&self, item: T) {
+        HAZARD_POINTER.with(|hp| self.enqueue_with(item, &mut hp.borrow_mut()));
+    }
+
+    /// Dequeue an item, borrowing this thread's hazard pointer from a thread-local pool.
+    ///
+    /// See [`FAAAQueue::enqueue`] for why this exists alongside [`FAAAQueue::dequeue_with`].
+    pub fn dequeue(&self) -> Option<T> {
+        HAZARD_POINTER.with(|hp| self.dequeue_with(&mut hp.borrow_mut()))
+    }
+
+    /// Enqueue `item`, using the caller-supplied hazard pointer to protect the load of `tail`.
+    pub fn enqueue_with(&self, item: T, hp: &mut HazardPointer) {
         let item_ptr = Box::into_raw(Box::new(item));
+        let backoff = Backoff::new();
         loop {
             let ltail = self.tail.safe_load(hp).unwrap();
             let idx = ltail.enqueue_index.fetch_add(1, SeqCst);
-            if idx > BUFFER_SIZE - 1 {
+            if idx > N - 1 {
                 // This node is full.
-                if ltail as *const _ != self.tail.load_ptr() {
+                if !std::ptr::eq(ltail, self.tail.load_ptr()) {
+                    pace(&backoff);
                     continue;
                 }
-                let lnext: *mut Node<T> = ltail.next.load_ptr();
+                let lnext: *mut Node<T, N> = ltail.next.load_ptr();
                 if lnext.is_null() {
-                    // NOTE: Must copy item_ptr? Otherwise it would be moved
-                    // out of scope?
-                    let new_node = Box::into_raw(Box::new(Node::new(item_ptr)));
+                    let new_node = match self.freelist.pop() {
+                        Some(recycled) => {
+                            unsafe { Node::reinit(recycled, item_ptr) };
+                            recycled
+                        }
+                        None => Box::into_raw(Box::new(Node::new(
+                            item_ptr,
+                            self.freelist.as_ref() as *const Freelist<T, N> as *mut Freelist<T, N>,
+                        ))),
+                    };
                     if unsafe {
                         ltail
                             .next
@@ -71,16 +301,24 @@ impl<T> FAAAQueue<T> {
                                 .compare_exchange_ptr(ltail as *const _ as *mut _, new_node)
                         };
                         hp.reset_protection();
+                        self.wake_one_waiter();
                         return;
                     }
-                    // NOTE: Fine since it is dropping the pointer to item,
-                    // which is a copy of item_ptr?
-                    unsafe { drop(Box::from_raw(new_node)) };
+                    // Someone else already linked in a new tail node first: retire ours so
+                    // `PooledNode::drop` gives it back to the freelist once no `Freelist::pop`
+                    // still protects it, so the lost race doesn't cost an allocation.
+                    unsafe {
+                        haphazard::Domain::global()
+                            .retire_ptr::<Node<T, N>, PooledNode<T, N>>(new_node)
+                    };
+                    pace(&backoff);
                 } else {
+                    // Helping a lagging tail catch up is progress, not contention.
                     let _ = unsafe {
                         self.tail
                             .compare_exchange_ptr(ltail as *const _ as *mut _, lnext)
                     };
+                    backoff.reset();
                 }
                 continue;
             }
@@ -90,11 +328,16 @@ impl<T> FAAAQueue<T> {
                 .is_ok()
             {
                 hp.reset_protection();
+                self.wake_one_waiter();
                 return;
             }
+            pace(&backoff);
         }
     }
-    pub fn dequeue(&self, hp: &mut HazardPointer) -> Option<T> {
+
+    /// Dequeue an item, using the caller-supplied hazard pointer to protect the load of `head`.
+    pub fn dequeue_with(&self, hp: &mut HazardPointer) -> Option<T> {
+        let backoff = Backoff::new();
         loop {
             let lhead = self.head.safe_load(hp).unwrap();
             if lhead.dequeue_index.load(SeqCst) >= lhead.enqueue_index.load(SeqCst)
@@ -103,7 +346,7 @@ impl<T> FAAAQueue<T> {
                 break;
             }
             let idx = lhead.dequeue_index.fetch_add(1, SeqCst);
-            if idx > BUFFER_SIZE - 1 {
+            if idx > N - 1 {
                 // Node has been drained
                 let lnext = lhead.next.load_ptr();
                 if lnext.is_null() {
@@ -113,14 +356,23 @@ impl<T> FAAAQueue<T> {
                     self.head
                         .compare_exchange_ptr(lhead as *const _ as *mut _, lnext)
                 } {
+                    // Retired into the global domain; once no hazard pointer protects it any
+                    // longer, `PooledNode::drop` recycles it into this queue's freelist.
                     unsafe {
                         old_ptr.unwrap().retire();
                     }
+                    backoff.reset();
+                } else {
+                    pace(&backoff);
                 }
                 continue;
             }
-            let item_ptr = lhead.array[idx].swap(1u64 as *mut u64 as *mut T, SeqCst);
+            let item_ptr = lhead.array[idx].swap(taken_sentinel(), SeqCst);
+            // `lhead` isn't read past this point (the next iteration re-protects `head`), and a
+            // node left protected after this call returns could not be reclaimed by `Drop`.
+            hp.reset_protection();
             if item_ptr.is_null() {
+                pace(&backoff);
                 continue;
             }
             let item = *unsafe { Box::from_raw(item_ptr) };
@@ -129,40 +381,386 @@ impl<T> FAAAQueue<T> {
         hp.reset_protection();
         None
     }
+
+    /// Returns an iterator that repeatedly [`dequeue`](FAAAQueue::dequeue)s, so every item
+    /// currently queued can be pulled out in one expression (e.g. `q.drain().collect()`).
+    pub fn drain(&self) -> Drain<'_, T, N> {
+        Drain { queue: self }
+    }
+
+    /// Like [`dequeue`](Self::dequeue), but parks the calling thread instead of returning `None`
+    /// when the queue is momentarily empty, waking up once some `enqueue` call publishes an item.
+    ///
+    /// This is a Treiber stack of parked waiters (the same pattern [`Freelist`] uses) that
+    /// `enqueue` pops and wakes one of on every successful publish, rather than a true
+    /// Scherer–Scott dual queue spliced into `Node`'s array: letting a slot hold either data or a
+    /// reservation would mean `enqueue_index`/`dequeue_index` could no longer be reasoned about
+    /// independently, and that interaction would need to be re-derived at every `N`-sized
+    /// boundary (full-node handoff, recycling, iteration) touched by the rest of this file. This
+    /// gets the same externally-visible behavior — block instead of spin, wake on the next
+    /// `enqueue` — without disturbing those invariants.
+    ///
+    /// Each blocking call costs an allocation plus a CAS-linked-list push, and an item is not
+    /// handed directly to the woken thread: it re-runs `dequeue` like any other consumer.
+    pub fn dequeue_blocking(&self) -> T {
+        loop {
+            if let Some(item) = self.dequeue() {
+                return item;
+            }
+            let waiter = Box::into_raw(Box::new(Waiter {
+                thread: std::thread::current(),
+                state: AtomicU8::new(Waiter::PARKED),
+                next: RawAtomicPtr::new(null_mut()),
+            }));
+            loop {
+                let top = self.waiters.load(SeqCst);
+                unsafe { (*waiter).next.store(top, SeqCst) };
+                if self
+                    .waiters
+                    .compare_exchange(top, waiter, SeqCst, SeqCst)
+                    .is_ok()
+                {
+                    break;
+                }
+            }
+            // An item may have been enqueued (and its wake-up handed to someone else, or missed
+            // entirely) in the gap between the failed `dequeue` above and linking `waiter` in, so
+            // check once more before committing to parking.
+            if let Some(item) = self.dequeue() {
+                let state = unsafe { &*waiter }.state.compare_exchange(
+                    Waiter::PARKED,
+                    Waiter::CANCELLED,
+                    SeqCst,
+                    SeqCst,
+                );
+                if state.is_err() {
+                    // `wake_one_waiter` already unlinked and claimed `waiter` (it lost the race
+                    // to set `FULFILLED`), so it's ours alone to free, same as the park path
+                    // below.
+                    unsafe { drop(Box::from_raw(waiter)) };
+                }
+                // Otherwise we won the race and marked ourselves `CANCELLED`: `waiter` may still
+                // be linked into `self.waiters`, so freeing it is left to whichever
+                // `wake_one_waiter` call eventually pops it (see the type's doc comment).
+                return item;
+            }
+            while unsafe { &*waiter }.state.load(SeqCst) != Waiter::FULFILLED {
+                std::thread::park();
+            }
+            // SAFETY: `state` only reaches `FULFILLED` once `wake_one_waiter` has unlinked
+            // `waiter` from the stack and is done touching it.
+            unsafe { drop(Box::from_raw(waiter)) };
+        }
+    }
+
+    /// Pop one parked waiter (if any) off the stack and wake it, so a consumer blocked in
+    /// [`dequeue_blocking`](Self::dequeue_blocking) re-checks the queue instead of staying
+    /// parked forever.
+    fn wake_one_waiter(&self) {
+        loop {
+            let top = self.waiters.load(SeqCst);
+            if top.is_null() {
+                return;
+            }
+            let next = unsafe { (*top).next.load(SeqCst) };
+            if self
+                .waiters
+                .compare_exchange(top, next, SeqCst, SeqCst)
+                .is_ok()
+            {
+                // SAFETY: `top` is now unlinked from `self.waiters`, so nothing else can observe
+                // or free it except via the `state` CAS below.
+                let waiter = unsafe { &*top };
+                // Cloned before the CAS: once `state` is `FULFILLED`, the waiting thread may free
+                // `top` at any moment.
+                let thread = waiter.thread.clone();
+                if waiter
+                    .state
+                    .compare_exchange(Waiter::PARKED, Waiter::FULFILLED, SeqCst, SeqCst)
+                    .is_ok()
+                {
+                    // We won: ownership of `top` passes to the waiting thread, which frees it
+                    // once it observes `FULFILLED`.
+                    thread.unpark();
+                    return;
+                }
+                // We lost: the waiting thread already self-satisfied and marked itself
+                // `CANCELLED` before we got here. It returned without touching `top` again, and
+                // we're the one who just unlinked it, so freeing it is ours alone. Keep looking
+                // for another waiter to actually wake.
+                unsafe { drop(Box::from_raw(top)) };
+            }
+        }
+    }
+
     pub fn new() -> Self {
-        let start_node = Box::into_raw(Box::new(Node::empty()));
+        const { assert!(N > 0, "FAAAQueue buffer size `N` must be greater than 0") };
+        let freelist = Box::new(Freelist::new());
+        let freelist_ptr = freelist.as_ref() as *const Freelist<T, N> as *mut Freelist<T, N>;
+        let start_node = Box::into_raw(Box::new(Node::empty(freelist_ptr)));
         Self {
             head: unsafe { HpAtomicPtr::new(start_node) },
             tail: unsafe { HpAtomicPtr::new(start_node) },
+            freelist,
+            waiters: RawAtomicPtr::new(null_mut()),
         }
     }
 }
 
-impl<T> Default for FAAAQueue<T> {
+impl<T, const N: usize> Default for FAAAQueue<T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T> Drop for FAAAQueue<T> {
-    fn drop(&mut self) {
-        let head: Box<Node<T>> = unsafe { Box::from_raw(self.head.load_ptr()) };
-        let mut next = head.next;
-
-        while !next.load_ptr().is_null() {
-            let node: Box<Node<T>> = unsafe { Box::from_raw(next.load_ptr()) };
-            for data in node.array {
-                let reclaimed_mem = data.load(SeqCst);
-                if !reclaimed_mem.is_null() {
-                    unsafe { drop(Box::from_raw(data.load(SeqCst))) };
+impl<T, const N: usize> IntoIterator for FAAAQueue<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    /// Consume the queue, yielding its remaining items in FIFO order.
+    ///
+    /// Exclusive ownership of `self` means no concurrent `enqueue`/`dequeue` can be in flight, so
+    /// the live head..tail chain can be walked and freed directly, without hazard pointers.
+    fn into_iter(self) -> IntoIter<T, N> {
+        haphazard::Domain::global().eager_reclaim();
+        let mut queue = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `queue` is wrapped in `ManuallyDrop`, so `FAAAQueue`'s own `Drop` (which would
+        // otherwise free this same node chain out from under `IntoIter`) never runs; this is the
+        // only place `freelist` is dropped.
+        unsafe { std::ptr::drop_in_place(&mut queue.freelist) };
+        free_waiters(queue.waiters.load(SeqCst));
+        IntoIter {
+            node: queue.head.load_ptr(),
+            idx: 0,
+        }
+    }
+}
+
+/// Iterator that repeatedly [`dequeue`](FAAAQueue::dequeue)s from a queue without consuming it.
+///
+/// Returned by [`FAAAQueue::drain`]. Since other threads may still be enqueueing concurrently,
+/// "empty" here just means "no item was available at the moment a given `next()` call checked" —
+/// it's not a snapshot of the queue at the time `drain` was called.
+pub struct Drain<'q, T, const N: usize = BUFFER_SIZE> {
+    queue: &'q FAAAQueue<T, N>,
+}
+
+impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.dequeue()
+    }
+}
+
+struct Slot<T> {
+    stamp: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A fixed-capacity MPMC queue built on Vyukov's bounded ring-buffer algorithm, the same design
+/// `crossbeam`'s `ArrayQueue` uses. Unlike [`FAAAQueue`], slots are reused in place rather than
+/// retired, so no hazard pointers are needed — at the cost of [`try_enqueue`](Self::try_enqueue)
+/// rejecting the item once the ring is full instead of growing to fit it.
+pub struct BoundedQueue<T> {
+    buffer: Box<[Slot<T>]>,
+    mask: usize,
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+// SAFETY: a `Slot<T>`'s `stamp` governs exclusive access to its `value`, exactly like
+// `FAAAQueue`'s per-slot CAS does for its `array` — so `BoundedQueue<T>` is Send/Sync under the
+// same conditions a channel of `T` would be.
+unsafe impl<T: Send> Send for BoundedQueue<T> {}
+unsafe impl<T: Send> Sync for BoundedQueue<T> {}
+
+impl<T> BoundedQueue<T> {
+    /// Create a queue that holds at most `capacity` items (rounded up to the next power of two).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        let buffer = (0..capacity)
+            .map(|i| Slot {
+                stamp: AtomicUsize::new(i),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        Self {
+            buffer,
+            mask: capacity - 1,
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Try to enqueue `item`, handing it back unchanged if the queue is full.
+    pub fn try_enqueue(&self, item: T) -> Result<(), T> {
+        let backoff = Backoff::new();
+        let mut tail = self.tail.load(SeqCst);
+        loop {
+            let slot = &self.buffer[tail & self.mask];
+            let stamp = slot.stamp.load(SeqCst);
+            let diff = stamp.wrapping_sub(tail) as isize;
+            if diff == 0 {
+                let new_tail = tail.wrapping_add(1);
+                match self
+                    .tail
+                    .compare_exchange_weak(tail, new_tail, SeqCst, SeqCst)
+                {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).write(item) };
+                        slot.stamp.store(new_tail, SeqCst);
+                        return Ok(());
+                    }
+                    Err(current) => {
+                        tail = current;
+                        pace(&backoff);
+                    }
                 }
+            } else if diff < 0 {
+                return Err(item);
+            } else {
+                tail = self.tail.load(SeqCst);
+                pace(&backoff);
             }
+        }
+    }
 
-            next = node.next;
+    /// Try to dequeue an item, returning `None` if the queue is empty.
+    pub fn try_dequeue(&self) -> Option<T> {
+        let backoff = Backoff::new();
+        let mut head = self.head.load(SeqCst);
+        loop {
+            let slot = &self.buffer[head & self.mask];
+            let stamp = slot.stamp.load(SeqCst);
+            let diff = stamp.wrapping_sub(head.wrapping_add(1)) as isize;
+            if diff == 0 {
+                let new_head = head.wrapping_add(1);
+                match self
+                    .head
+                    .compare_exchange_weak(head, new_head, SeqCst, SeqCst)
+                {
+                    Ok(_) => {
+                        let item = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.stamp.store(head.wrapping_add(self.mask + 1), SeqCst);
+                        return Some(item);
+                    }
+                    Err(current) => {
+                        head = current;
+                        pace(&backoff);
+                    }
+                }
+            } else if diff < 0 {
+                return None;
+            } else {
+                head = self.head.load(SeqCst);
+                pace(&backoff);
+            }
         }
     }
 }
 
+impl<T> std::fmt::Debug for BoundedQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedQueue")
+            .field("capacity", &(self.mask + 1))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for BoundedQueue<T> {
+    fn drop(&mut self) {
+        // No other handle to `self` can exist (we hold `&mut self`), so every slot between
+        // `head` and `tail` still holds an initialized, not-yet-dequeued item to drop.
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        while head != tail {
+            let slot = &mut self.buffer[head & self.mask];
+            unsafe { slot.value.get_mut().assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for FAAAQueue<T, N> {
+    fn drop(&mut self) {
+        // Force reclamation of any node this queue has already retired. Since no other thread
+        // can be mid-`enqueue`/`dequeue` on `self` right now (we hold `&mut self`), nothing is
+        // protecting them any more, so this is guaranteed to recycle or free every one of them
+        // before `self.freelist` is torn down below.
+        haphazard::Domain::global().eager_reclaim();
+
+        // Walking (and freeing) the live head..tail chain is exactly what `IntoIter` does;
+        // dropping it immediately drains and frees every remaining node. `self.freelist`'s own
+        // `Drop` frees anything still pooled once this function returns.
+        let _ = IntoIter::<T, N> {
+            node: self.head.load_ptr(),
+            idx: 0,
+        };
+        free_waiters(*self.waiters.get_mut());
+    }
+}
+
+/// Free a chain of [`Waiter`]s left dangling in a queue's `waiters` stack (the rare case where a
+/// consumer won its own race against `wake_one_waiter` and returned without being popped — see
+/// [`FAAAQueue::dequeue_blocking`]). Safe to call once exclusive access to the queue is held,
+/// since nothing can still be parked waiting on these without a reference to the queue itself.
+fn free_waiters(mut waiter: *mut Waiter) {
+    while !waiter.is_null() {
+        let next = unsafe { (*waiter).next.load(SeqCst) };
+        unsafe { drop(Box::from_raw(waiter)) };
+        waiter = next;
+    }
+}
+
+/// Consuming iterator over the items still in a [`FAAAQueue`], in FIFO order.
+///
+/// Returned by [`FAAAQueue::into_iter`]. Owns the live head..tail node chain, freeing each node
+/// as it's drained; dropping the iterator before it's exhausted still frees every remaining node
+/// (and drops every remaining item), so partial iteration (e.g. `.take(3)`) can't leak.
+pub struct IntoIter<T, const N: usize = BUFFER_SIZE> {
+    node: *mut Node<T, N>,
+    idx: usize,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if self.node.is_null() {
+                return None;
+            }
+            // SAFETY: `self.node` was produced by `Box::into_raw` (either as a fresh node or a
+            // recycled one) and nothing else can be accessing it: `IntoIter` is the sole owner
+            // of the whole remaining chain, so walking past the end of `array` and on to `next`
+            // (freeing each node as it's fully drained) can't race anything.
+            let node = unsafe { &*self.node };
+            if self.idx >= N {
+                let next = node.next.load_ptr();
+                unsafe { drop(Box::from_raw(self.node)) };
+                self.node = next;
+                self.idx = 0;
+                continue;
+            }
+            let item_ptr = node.array[self.idx].load(SeqCst);
+            self.idx += 1;
+            if item_ptr.is_null() || item_ptr == taken_sentinel() {
+                continue;
+            }
+            return Some(*unsafe { Box::from_raw(item_ptr) });
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        // Drain (and free) whatever this iterator didn't consume, so dropping it early (e.g.
+        // after `.take(3)`) can't leak items or nodes.
+        for _ in self.by_ref() {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -171,30 +769,214 @@ mod tests {
     #[test]
     fn create_faaaq_queue() {
         let q: FAAAQueue<i32> = FAAAQueue::new();
-        let mut hp = HazardPointer::new();
-        q.enqueue(1, &mut hp);
-        assert_eq!(q.dequeue(&mut hp), Some(1));
+        q.enqueue(1);
+        assert_eq!(q.dequeue(), Some(1));
     }
     #[test]
     fn test_almost_full() {
         let q: FAAAQueue<usize> = FAAAQueue::new();
-        let mut hp = HazardPointer::new();
         for i in 0..BUFFER_SIZE {
-            q.enqueue(i, &mut hp);
+            q.enqueue(i);
         }
         for i in 0..BUFFER_SIZE {
-            assert_eq!(q.dequeue(&mut hp), Some(i));
+            assert_eq!(q.dequeue(), Some(i));
         }
     }
     #[test]
     fn test_double_buf_size() {
         let q: FAAAQueue<usize> = FAAAQueue::new();
-        let mut hp = HazardPointer::new();
         for i in 0..BUFFER_SIZE * 2 {
-            q.enqueue(i, &mut hp);
+            q.enqueue(i);
         }
         for i in 0..BUFFER_SIZE * 2 {
-            assert_eq!(q.dequeue(&mut hp), Some(i));
+            assert_eq!(q.dequeue(), Some(i));
+        }
+    }
+    #[test]
+    fn explicit_hazard_pointer() {
+        let q: FAAAQueue<i32> = FAAAQueue::new();
+        let mut hp = HazardPointer::new();
+        q.enqueue_with(1, &mut hp);
+        assert_eq!(q.dequeue_with(&mut hp), Some(1));
+    }
+    #[test]
+    fn recycles_drained_node_buffers() {
+        let q: FAAAQueue<usize> = FAAAQueue::new();
+        for lap in 0..4 {
+            for i in 0..BUFFER_SIZE {
+                q.enqueue(lap * BUFFER_SIZE + i);
+            }
+            for i in 0..BUFFER_SIZE {
+                assert_eq!(q.dequeue(), Some(lap * BUFFER_SIZE + i));
+            }
+        }
+        // Reclamation is batched by the domain, so force it before checking that a drained
+        // node actually made it back into the pool.
+        haphazard::Domain::global().eager_reclaim();
+        assert!(q.freelist.len.load(SeqCst) > 0);
+    }
+    #[test]
+    fn recycled_node_second_life_has_no_stale_sentinels() {
+        // A small `N` forces a node's second life almost immediately: fill and fully drain node
+        // A, then fill and drain node B (moving `head` on to B retires A), force reclamation so
+        // A is recycled, and the next new node (for items 8..11) is A handed back out. Every slot
+        // of A must behave as freshly empty, not still hold a stale `taken_sentinel()` from its
+        // first life.
+        let q: FAAAQueue<usize, 4> = FAAAQueue::new();
+        for i in 0..4 {
+            q.enqueue(i);
+        }
+        for i in 0..4 {
+            assert_eq!(q.dequeue(), Some(i));
+        }
+        haphazard::Domain::global().eager_reclaim();
+        for i in 4..8 {
+            q.enqueue(i);
+        }
+        for i in 4..8 {
+            assert_eq!(q.dequeue(), Some(i));
+        }
+        haphazard::Domain::global().eager_reclaim();
+        for i in 8..16 {
+            q.enqueue(i);
+        }
+        for i in 8..16 {
+            assert_eq!(q.dequeue(), Some(i));
+        }
+    }
+    #[test]
+    fn into_iter_yields_remaining_items_in_order() {
+        let q: FAAAQueue<usize> = FAAAQueue::new();
+        for i in 0..BUFFER_SIZE * 2 + 1 {
+            q.enqueue(i);
+        }
+        assert_eq!(q.dequeue(), Some(0));
+        let collected: Vec<usize> = q.into_iter().collect();
+        assert_eq!(collected, (1..BUFFER_SIZE * 2 + 1).collect::<Vec<_>>());
+    }
+    #[test]
+    fn into_iter_partial_consumption_does_not_leak() {
+        let q: FAAAQueue<usize> = FAAAQueue::new();
+        for i in 0..BUFFER_SIZE * 2 {
+            q.enqueue(i);
+        }
+        // Only consuming part of the iterator still has to free the rest when it's dropped.
+        assert_eq!(q.into_iter().take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+    #[test]
+    fn drain_empties_the_queue_without_consuming_it() {
+        let q: FAAAQueue<usize> = FAAAQueue::new();
+        for i in 0..BUFFER_SIZE + 5 {
+            q.enqueue(i);
+        }
+        let drained: Vec<usize> = q.drain().collect();
+        assert_eq!(drained, (0..BUFFER_SIZE + 5).collect::<Vec<_>>());
+        assert_eq!(q.dequeue(), None);
+        q.enqueue(42);
+        assert_eq!(q.dequeue(), Some(42));
+    }
+    #[test]
+    fn bounded_queue_rejects_enqueue_once_full() {
+        let q: BoundedQueue<usize> = BoundedQueue::new(4);
+        for i in 0..4 {
+            assert_eq!(q.try_enqueue(i), Ok(()));
+        }
+        assert_eq!(q.try_enqueue(4), Err(4));
+        assert_eq!(q.try_dequeue(), Some(0));
+        assert_eq!(q.try_enqueue(4), Ok(()));
+    }
+    #[test]
+    fn bounded_queue_empty_dequeue_returns_none() {
+        let q: BoundedQueue<usize> = BoundedQueue::new(4);
+        assert_eq!(q.try_dequeue(), None);
+    }
+    #[test]
+    fn bounded_queue_rounds_capacity_up_to_power_of_two() {
+        let q: BoundedQueue<usize> = BoundedQueue::new(5);
+        for i in 0..8 {
+            assert_eq!(q.try_enqueue(i), Ok(()));
+        }
+        assert_eq!(q.try_enqueue(8), Err(8));
+    }
+    #[test]
+    fn bounded_queue_survives_many_wraparounds_in_fifo_order() {
+        let q: BoundedQueue<usize> = BoundedQueue::new(4);
+        for i in 0..1000 {
+            assert_eq!(q.try_enqueue(i), Ok(()));
+            assert_eq!(q.try_dequeue(), Some(i));
+        }
+    }
+    #[test]
+    fn dequeue_blocking_wakes_once_an_item_is_enqueued() {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let q = Arc::new(FAAAQueue::<usize>::new());
+        let consumer = {
+            let q = Arc::clone(&q);
+            std::thread::spawn(move || q.dequeue_blocking())
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        q.enqueue(7);
+        assert_eq!(consumer.join().unwrap(), 7);
+    }
+    #[test]
+    fn dequeue_blocking_returns_immediately_if_already_populated() {
+        let q: FAAAQueue<usize> = FAAAQueue::new();
+        q.enqueue(3);
+        assert_eq!(q.dequeue_blocking(), 3);
+    }
+    #[test]
+    fn dequeue_blocking_many_producers_and_consumers() {
+        // Exercises the race between a parked consumer self-satisfying its own wait (via the
+        // redundant post-link `dequeue()` check) and `wake_one_waiter` popping the same waiter,
+        // which is exactly the window the `Waiter::state` CAS scheme has to get right: every
+        // item enqueued must be observed by exactly one consumer, with no leaked or
+        // double-freed `Waiter`.
+        use std::sync::Arc;
+
+        let q = Arc::new(FAAAQueue::<usize>::new());
+        const ITEMS_PER_PRODUCER: usize = 2000;
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|_| {
+                let q = Arc::clone(&q);
+                std::thread::spawn(move || {
+                    for i in 0..ITEMS_PER_PRODUCER {
+                        q.enqueue(i);
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let q = Arc::clone(&q);
+                std::thread::spawn(move || {
+                    let mut count = 0;
+                    for _ in 0..(PRODUCERS * ITEMS_PER_PRODUCER / CONSUMERS) {
+                        q.dequeue_blocking();
+                        count += 1;
+                    }
+                    count
+                })
+            })
+            .collect();
+        for p in producers {
+            p.join().unwrap();
+        }
+        let total: usize = consumers.into_iter().map(|c| c.join().unwrap()).sum();
+        assert_eq!(total, PRODUCERS * ITEMS_PER_PRODUCER);
+    }
+    #[test]
+    fn small_buffer_size_can_be_chosen_via_const_generic() {
+        let q: FAAAQueue<usize, 4> = FAAAQueue::new();
+        for i in 0..10 {
+            q.enqueue(i);
+        }
+        for i in 0..10 {
+            assert_eq!(q.dequeue(), Some(i));
         }
     }
 }